@@ -17,42 +17,119 @@
 //! ```
 //!
 
-#[derive(PartialEq, Debug)]
-pub struct PatternElement(pub Option<u8>);
+mod signature_set;
+pub use signature_set::SignatureSet;
 
-impl std::cmp::PartialEq<u8> for PatternElement {
-    fn eq(&self, other: &u8) -> bool {
-        self.0.map(|b| b == *other).unwrap_or(true)
+#[cfg(feature = "rayon")]
+mod parallel;
+
+mod pattern_element;
+pub use pattern_element::PatternElement;
+
+mod searcher;
+pub use searcher::Searcher;
+
+/// Rough frequency ranking over 0..=255 used to pick a scan pivot byte: higher means more
+/// common in typical binary/text haystacks (and thus a worse pivot), lower means rarer.
+fn byte_commonness(byte: u8) -> u8 {
+    match byte {
+        0x00 | 0xFF => 200,
+        b'a'..=b'z' | b'A'..=b'Z' => 180,
+        b'0'..=b'9' => 120,
+        0x20 => 150,
+        _ => 10,
     }
 }
 
-pub struct Signature(Vec<PatternElement>);
+pub struct Signature {
+    pattern: Vec<PatternElement>,
+    /// Index of the first element of the trailing wildcard-free run of `pattern` (the "tail"
+    /// used to build `skip_table`). Equal to `pattern.len()` if the pattern ends in a wildcard
+    /// or contains no concrete bytes at all.
+    tail_start: usize,
+    /// Boyer-Moore-Horspool bad-character skip table, built only over the tail since wildcards
+    /// before it break the classic skip rule. `skip_table[b]` is how far the cursor can jump
+    /// when the byte aligned with the tail's last position is `b`.
+    skip_table: [usize; 256],
+    /// The rarest concrete byte in the pattern and its offset, used as a `memchr` prefilter.
+    /// `None` when the pattern has no concrete bytes at all.
+    pivot: Option<(u8, usize)>,
+}
 
 impl Signature {
+    fn from_pattern(pattern: Vec<PatternElement>) -> Self {
+        let (tail_start, skip_table) = Self::build_skip_table(&pattern);
+        let pivot = Self::find_pivot(&pattern);
+        Self {
+            pattern,
+            tail_start,
+            skip_table,
+            pivot,
+        }
+    }
+
+    fn build_skip_table(pattern: &[PatternElement]) -> (usize, [usize; 256]) {
+        let tail_start = pattern
+            .iter()
+            .rposition(|element| !element.is_concrete())
+            .map(|index| index + 1)
+            .unwrap_or(0);
+
+        let tail = &pattern[tail_start..];
+        let len = tail.len();
+        let mut skip_table = [len; 256];
+
+        if len > 0 {
+            for (i, element) in tail[..len - 1].iter().enumerate() {
+                // Every element in the tail is concrete by construction of `tail_start`.
+                skip_table[element.value() as usize] = len - 1 - i;
+            }
+        }
+
+        (tail_start, skip_table)
+    }
+
+    /// Picks the rarest concrete byte in the pattern to use as a `memchr` prefilter, along with
+    /// its offset from the start of the pattern. Nibble-masked elements are not concrete enough
+    /// to pivot on, the same as full wildcards.
+    fn find_pivot(pattern: &[PatternElement]) -> Option<(u8, usize)> {
+        pattern
+            .iter()
+            .enumerate()
+            .filter(|(_, element)| element.is_concrete())
+            .map(|(offset, element)| (offset, element.value()))
+            .min_by_key(|&(_, byte)| byte_commonness(byte))
+            .map(|(offset, byte)| (byte, offset))
+    }
+
     /// Creates a signature with the pattern being taken directly from the vector
     pub fn new(vec: Vec<PatternElement>) -> Self {
-        Self(vec)
+        Self::from_pattern(vec)
     }
 
-    /// Creates a signature with the pattern being a ida-style bytes sequence
+    /// Creates a signature with the pattern being a ida-style bytes sequence. Each hex nibble of
+    /// a word may independently be `?`, so `A?`, `?4` and `??` are all valid wildcards alongside
+    /// a fully concrete byte like `A4`.
     pub fn ida(pattern: &str) -> Self {
-        Self(
+        Self::from_pattern(
             pattern
                 .split_ascii_whitespace()
                 .map(|word| {
                     if word.chars().all(|c| c == '?') {
-                        PatternElement(None)
+                        PatternElement::WILDCARD
                     } else {
-                        PatternElement(Some(
-                            word.chars()
-                                .rev()
-                                .map(|c| c.to_digit(16))
-                                .map(|opt| opt.unwrap())
-                                .enumerate()
-                                .map(|(i, num)| if i == 0 { num } else { num * (i * 16) as u32 })
-                                .map(|num| num as u8)
-                                .sum(),
-                        ))
+                        let mut value = 0u8;
+                        let mut mask = 0u8;
+                        for c in word.chars() {
+                            value <<= 4;
+                            mask <<= 4;
+                            if c != '?' {
+                                let nibble = c.to_digit(16).expect("invalid ida pattern nibble");
+                                value |= nibble as u8;
+                                mask |= 0x0F;
+                            }
+                        }
+                        PatternElement::masked(value, mask)
                     }
                 })
                 .collect::<Vec<_>>(),
@@ -64,14 +141,14 @@ impl Signature {
         let mut elements = Vec::new();
 
         for c in string.chars() {
-            elements.push(PatternElement(Some(c as u8)))
+            elements.push(PatternElement::exact(c as u8))
         }
 
         if include_terminator {
-            elements.push(PatternElement(Some(0x00u8)))
+            elements.push(PatternElement::exact(0x00u8))
         }
 
-        Self(elements)
+        Self::from_pattern(elements)
     }
 
     /// Creates a signature with the pattern being a string with wildcards
@@ -80,50 +157,119 @@ impl Signature {
 
         for c in string.chars() {
             if c == wildcard {
-                elements.push(PatternElement(None))
+                elements.push(PatternElement::WILDCARD)
             } else {
-                elements.push(PatternElement(Some(c as u8)))
+                elements.push(PatternElement::exact(c as u8))
             }
         }
 
         if include_terminator {
-            elements.push(PatternElement(Some(0x00u8)))
+            elements.push(PatternElement::exact(0x00u8))
         }
 
-        Self(elements)
+        Self::from_pattern(elements)
+    }
+
+    /// Finds the position of a match starting at or after `start`.
+    ///
+    /// When the pattern ends in a run of concrete bytes (the common case for exact byte
+    /// sequences and strings, where the tail is the whole pattern), the Boyer-Moore-Horspool
+    /// skip table built over that run is used to jump ahead on mismatches. Otherwise, if there's
+    /// a concrete byte anywhere else in the pattern (e.g. `48 8B 05 ?? ?? ?? ??`), `memchr` is
+    /// used to jump straight to candidate positions instead of testing every window. Patterns
+    /// with no concrete byte at all either match everywhere (all wildcards) or fall back to
+    /// verifying every window directly (nibble-masked elements with nothing to skip ahead on).
+    fn find_from(&self, slice: &[u8], start: usize) -> Option<usize> {
+        let pattern_len = self.pattern.len();
+        if pattern_len == 0 || start > slice.len() || slice.len() - start < pattern_len {
+            return None;
+        }
+
+        if self.tail_start < pattern_len {
+            return self.find_from_bmh(slice, start);
+        }
+
+        if let Some((pivot_byte, pivot_offset)) = self.pivot {
+            let mut search_from = start + pivot_offset;
+            while search_from < slice.len() {
+                let found = search_from + memchr::memchr(pivot_byte, &slice[search_from..])?;
+                // `found >= search_from >= pivot_offset`, so this never underflows.
+                let candidate = found - pivot_offset;
+                if candidate + pattern_len <= slice.len()
+                    && self.pattern == slice[candidate..candidate + pattern_len]
+                {
+                    return Some(candidate);
+                }
+                search_from = found + 1;
+            }
+            return None;
+        }
+
+        if self.pattern.iter().all(|element| element.is_wildcard()) {
+            // Every element matches unconditionally, so every aligned position matches.
+            return Some(start);
+        }
+
+        // No concrete tail and no concrete byte anywhere to anchor on, but at least one
+        // nibble-masked element keeps this from being an unconditional match: nothing to skip
+        // ahead on, so verify each window directly.
+        let mut pos = start;
+        while pos + pattern_len <= slice.len() {
+            if self.pattern == slice[pos..pos + pattern_len] {
+                return Some(pos);
+            }
+            pos += 1;
+        }
+        None
+    }
+
+    /// Boyer-Moore-Horspool scan over the pattern's wildcard-free tail, used whenever the
+    /// pattern ends in at least one concrete byte.
+    fn find_from_bmh(&self, slice: &[u8], start: usize) -> Option<usize> {
+        let pattern_len = self.pattern.len();
+        let mut pos = start;
+        while pos + pattern_len <= slice.len() {
+            let window = &slice[pos..pos + pattern_len];
+            if self.pattern == window {
+                return Some(pos);
+            }
+
+            let tail_end_byte = slice[pos + pattern_len - 1];
+            pos += self.skip_table[tail_end_byte as usize];
+        }
+
+        None
+    }
+
+    /// Creates a [`Searcher`] over `slice`, allowing forward and backward probing to be
+    /// interleaved from both ends of the same buffer without restarting the scan.
+    pub fn search<'a>(&'a self, slice: &'a [u8]) -> Searcher<'a> {
+        Searcher::new(self, slice)
     }
 
     /// Finds the next occurrence of the pattern in `slice`
     pub fn next(&self, slice: &[u8]) -> Option<usize> {
-        slice
-            .windows(self.0.len())
-            .position(|window| self.0 == window)
+        self.search(slice).next_match()
     }
 
     /// Finds the previous occurrence of the pattern in `slice`
     pub fn prev(&self, slice: &[u8]) -> Option<usize> {
-        slice
-            .windows(self.0.len())
-            .rev()
-            .position(|window| self.0 == window)
-            .map(|offset| offset + self.0.len() - 1)
+        self.search(slice)
+            .next_match_back()
+            .map(|pos| slice.len() - 1 - pos)
     }
 
     /// Finds all occurrences of the pattern in `slice`
-    pub fn all<'a>(&'a self, slice: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
-        slice
-            .windows(self.0.len())
-            .enumerate()
-            .filter(|(_, window)| self.0 == *window)
-            .map(|(i, _)| i)
+    pub fn all<'a>(&'a self, slice: &'a [u8]) -> Searcher<'a> {
+        self.search(slice)
     }
 
     pub fn matches(&self, slice: &[u8]) -> bool {
-        self.0 == slice
+        self.pattern == slice
     }
 
     pub fn get_elements(&self) -> &Vec<PatternElement> {
-        &self.0
+        &self.pattern
     }
 }
 
@@ -134,47 +280,79 @@ mod tests {
     #[test]
     fn test_ida_construction() {
         assert_eq!(
-            Signature::ida("AA BB CC DD EE FF").0,
+            Signature::ida("AA BB CC DD EE FF").pattern,
             [0xAAu8, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]
         );
         assert_eq!(
-            Signature::ida("12 34 56 78 89").0,
+            Signature::ida("12 34 56 78 89").pattern,
             [0x12u8, 0x34, 0x56, 0x78, 0x89]
         );
         assert_eq!(
-            Signature::ida("12    34      56 \t78\t89").0,
+            Signature::ida("12    34      56 \t78\t89").pattern,
             [0x12u8, 0x34, 0x56, 0x78, 0x89]
         );
     }
 
     #[test]
     fn test_string_construction() {
-        assert_eq!(Signature::string("Test", false).0, [b'T', b'e', b's', b't']);
         assert_eq!(
-            Signature::string("Test", true).0,
+            Signature::string("Test", false).pattern,
+            [b'T', b'e', b's', b't']
+        );
+        assert_eq!(
+            Signature::string("Test", true).pattern,
             [b'T', b'e', b's', b't', b'\0']
         );
         assert_eq!(
-            Signature::wildcard_string("T?st", '?', false).0,
+            Signature::wildcard_string("T?st", '?', false).pattern,
             [
-                PatternElement(Some(b'T')),
-                PatternElement(None),
-                PatternElement(Some(b's')),
-                PatternElement(Some(b't'))
+                PatternElement::exact(b'T'),
+                PatternElement::WILDCARD,
+                PatternElement::exact(b's'),
+                PatternElement::exact(b't')
             ]
         );
         assert_eq!(
-            Signature::wildcard_string("T?st", '?', true).0,
+            Signature::wildcard_string("T?st", '?', true).pattern,
             [
-                PatternElement(Some(b'T')),
-                PatternElement(None),
-                PatternElement(Some(b's')),
-                PatternElement(Some(b't')),
-                PatternElement(Some(b'\0'))
+                PatternElement::exact(b'T'),
+                PatternElement::WILDCARD,
+                PatternElement::exact(b's'),
+                PatternElement::exact(b't'),
+                PatternElement::exact(b'\0')
             ]
         );
     }
 
+    #[test]
+    fn test_ida_nibble_wildcard_construction() {
+        assert_eq!(
+            Signature::ida("A? ?4 ??").pattern,
+            [
+                PatternElement::masked(0xA0, 0xF0),
+                PatternElement::masked(0x04, 0x0F),
+                PatternElement::WILDCARD
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ida_nibble_wildcard_matching() {
+        let signature = Signature::ida("48 8B ?5 ?? E8");
+        assert_eq!(
+            signature.next(&[0x00, 0x48, 0x8B, 0x15, 0x11, 0xE8, 0x22]),
+            Some(1)
+        );
+        assert_eq!(
+            signature.next(&[0x00, 0x48, 0x8B, 0x25, 0x11, 0xE8, 0x22]),
+            Some(1)
+        );
+        assert_eq!(
+            signature.next(&[0x00, 0x48, 0x8B, 0x16, 0x11, 0xE8, 0x22]),
+            None
+        );
+    }
+
     #[test]
     fn test_next_search_behavior() {
         assert_eq!(
@@ -226,4 +404,21 @@ mod tests {
 
         assert!(Signature::string("lo, wor", false).matches("lo, wor".as_bytes()));
     }
+
+    #[test]
+    fn test_wildcard_only_pattern() {
+        assert_eq!(
+            Signature::ida("? ? ?").next(&[0x00u8, 0x11, 0x22, 0x33]),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_masked_only_pattern_is_not_all_wildcard() {
+        // A single nibble-masked element has no concrete byte anywhere, but it still constrains
+        // the high nibble, so it must not be treated as an unconditional match everywhere.
+        let signature = Signature::ida("A?");
+        assert_eq!(signature.all(&[0x00, 0xA1, 0x22]).collect::<Vec<_>>(), [1]);
+        assert_eq!(signature.next(&[0x00, 0xA1, 0x22]), Some(1));
+    }
 }
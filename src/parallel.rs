@@ -0,0 +1,118 @@
+use rayon::prelude::*;
+
+use crate::Signature;
+
+/// Chunks smaller than this aren't worth splitting further; scanning it sequentially in one
+/// task amortizes the overhead of spawning work on `rayon`'s thread pool.
+const MIN_CHUNK_SIZE: usize = 4096;
+
+impl Signature {
+    /// Splits `len` bytes into chunks sized for `rayon`'s thread pool, each overlapping the next
+    /// by `pattern.len() - 1` bytes so a match straddling a chunk boundary is never missed.
+    fn chunk_bounds(&self, len: usize) -> Vec<(usize, usize)> {
+        let pattern_len = self.get_elements().len();
+        if pattern_len == 0 || len <= pattern_len {
+            return vec![(0, len)];
+        }
+
+        let overlap = pattern_len - 1;
+        let chunk_size = (len / rayon::current_num_threads().max(1))
+            .max(pattern_len)
+            .max(MIN_CHUNK_SIZE);
+
+        let mut bounds = Vec::new();
+        let mut start = 0;
+        loop {
+            let end = (start + chunk_size).min(len);
+            bounds.push((start, end));
+            if end == len {
+                break;
+            }
+            start = end - overlap;
+        }
+        bounds
+    }
+
+    /// Parallel counterpart to [`Signature::next`] for large haystacks, such as full memory
+    /// dumps. The haystack is split into overlapping chunks and scanned concurrently via
+    /// `rayon`, then the lowest matching index is returned, same as the sequential scan.
+    pub fn next_parallel(&self, slice: &[u8]) -> Option<usize> {
+        self.chunk_bounds(slice.len())
+            .into_par_iter()
+            .filter_map(|(start, end)| {
+                self.find_from(&slice[start..end], 0).map(|pos| start + pos)
+            })
+            .min()
+    }
+
+    /// Parallel counterpart to [`Signature::all`] for large haystacks.
+    ///
+    /// Matches are collected from concurrently scanned, overlapping chunks and then sorted so
+    /// the result order matches the sequential [`Signature::all`].
+    pub fn all_parallel(&self, slice: &[u8]) -> Vec<usize> {
+        let mut matches: Vec<usize> = self
+            .chunk_bounds(slice.len())
+            .into_par_iter()
+            .flat_map_iter(|(start, end)| self.all(&slice[start..end]).map(move |pos| start + pos))
+            .collect();
+
+        matches.sort_unstable();
+        matches.dedup();
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_parallel_matches_sequential() {
+        let haystack: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let signature = Signature::ida("0A 0B 0C");
+
+        assert_eq!(signature.next_parallel(&haystack), signature.next(&haystack));
+    }
+
+    #[test]
+    fn test_all_parallel_matches_sequential() {
+        let haystack: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let signature = Signature::ida("0A 0B 0C");
+
+        assert_eq!(
+            signature.all_parallel(&haystack),
+            signature.all(&haystack).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_chunk_bounds_overlap_by_pattern_len_minus_one() {
+        let signature = Signature::ida("DE AD BE");
+        let bounds = signature.chunk_bounds(50_000);
+
+        assert_eq!(bounds.first().unwrap().0, 0);
+        assert_eq!(bounds.last().unwrap().1, 50_000);
+        for window in bounds.windows(2) {
+            let [(_, prev_end), (next_start, _)] = window else {
+                unreachable!()
+            };
+            assert_eq!(*prev_end - *next_start, 2);
+        }
+    }
+
+    #[test]
+    fn test_parallel_finds_match_spanning_a_chunk_boundary() {
+        let signature = Signature::ida("DE AD BE");
+        let boundary = match signature.chunk_bounds(50_000).first() {
+            Some((_, end)) if *end < 50_000 => *end,
+            _ => return, // Single-threaded pool: nothing to straddle.
+        };
+
+        let mut haystack = vec![0u8; 50_000];
+        haystack[boundary - 1] = 0xDE;
+        haystack[boundary] = 0xAD;
+        haystack[boundary + 1] = 0xBE;
+
+        assert_eq!(signature.next_parallel(&haystack), Some(boundary - 1));
+    }
+}
@@ -0,0 +1,100 @@
+/// A single element of a [`Signature`](crate::Signature) pattern: a byte value together with a
+/// mask of which bits actually have to match. A `mask` bit of `0` means "don't care", so
+/// `mask == 0x00` is a full wildcard and `mask == 0xFF` is an exact byte. Bits in between allow
+/// FLIRT/IDA-style nibble wildcards such as `A?` (`mask == 0xF0`) or `?4` (`mask == 0x0F`).
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct PatternElement {
+    value: u8,
+    mask: u8,
+}
+
+impl PatternElement {
+    /// A full wildcard, matching any byte.
+    pub const WILDCARD: PatternElement = PatternElement { value: 0, mask: 0x00 };
+
+    /// Creates an element that must match `byte` exactly.
+    pub fn exact(byte: u8) -> Self {
+        Self {
+            value: byte,
+            mask: 0xFF,
+        }
+    }
+
+    /// Creates a masked element: bits set in `mask` must match the corresponding bits of
+    /// `value`, bits unset in `mask` match anything.
+    pub fn masked(value: u8, mask: u8) -> Self {
+        Self {
+            value: value & mask,
+            mask,
+        }
+    }
+
+    /// Whether this element pins down every bit of the byte it matches.
+    pub(crate) fn is_concrete(&self) -> bool {
+        self.mask == 0xFF
+    }
+
+    /// Whether this element is a full wildcard, matching any byte unconditionally.
+    pub(crate) fn is_wildcard(&self) -> bool {
+        self.mask == 0x00
+    }
+
+    /// The exact byte this element matches. Only meaningful when [`Self::is_concrete`].
+    pub(crate) fn value(&self) -> u8 {
+        self.value
+    }
+}
+
+impl From<Option<u8>> for PatternElement {
+    fn from(byte: Option<u8>) -> Self {
+        match byte {
+            Some(byte) => PatternElement::exact(byte),
+            None => PatternElement::WILDCARD,
+        }
+    }
+}
+
+impl std::cmp::PartialEq<u8> for PatternElement {
+    fn eq(&self, other: &u8) -> bool {
+        (other & self.mask) == self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_element() {
+        let element = PatternElement::exact(0x4A);
+        assert_eq!(element, 0x4Au8);
+        assert_ne!(element, 0x4Bu8);
+    }
+
+    #[test]
+    fn test_wildcard_element() {
+        assert_eq!(PatternElement::WILDCARD, 0x00u8);
+        assert_eq!(PatternElement::WILDCARD, 0xFFu8);
+    }
+
+    #[test]
+    fn test_nibble_masked_elements() {
+        // "A?": high nibble pinned, low nibble don't-care.
+        let high_pinned = PatternElement::masked(0xA0, 0xF0);
+        assert_eq!(high_pinned, 0xA0u8);
+        assert_eq!(high_pinned, 0xAFu8);
+        assert_ne!(high_pinned, 0xB0u8);
+
+        // "?4": low nibble pinned, high nibble don't-care.
+        let low_pinned = PatternElement::masked(0x04, 0x0F);
+        assert_eq!(low_pinned, 0x04u8);
+        assert_eq!(low_pinned, 0xF4u8);
+        assert_ne!(low_pinned, 0xF5u8);
+    }
+
+    #[test]
+    fn test_option_conversion() {
+        assert_eq!(PatternElement::from(Some(0x4A)), PatternElement::exact(0x4A));
+        assert_eq!(PatternElement::from(None), PatternElement::WILDCARD);
+    }
+}
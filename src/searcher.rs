@@ -0,0 +1,101 @@
+use crate::Signature;
+
+/// A resumable search over a haystack, modeled on `core::str::pattern`'s `Searcher`. Holds the
+/// haystack and a front/back cursor so a caller can interleave forward and backward probing
+/// (via [`Self::next_match`] and [`Self::next_match_back`]) without restarting the scan, and can
+/// iterate it directly since `Searcher` implements [`Iterator`] and [`DoubleEndedIterator`].
+pub struct Searcher<'a> {
+    signature: &'a Signature,
+    haystack: &'a [u8],
+    /// Matches are only looked for at or after this position.
+    front: usize,
+    /// Matches are only looked for fully inside `haystack[..back]`.
+    back: usize,
+}
+
+impl<'a> Searcher<'a> {
+    pub(crate) fn new(signature: &'a Signature, haystack: &'a [u8]) -> Self {
+        Self {
+            signature,
+            haystack,
+            front: 0,
+            back: haystack.len(),
+        }
+    }
+
+    /// Finds the next match at or after the front cursor, then advances the cursor one byte past
+    /// its start so the next call can still find an overlapping match.
+    pub fn next_match(&mut self) -> Option<usize> {
+        if self.front > self.back {
+            return None;
+        }
+
+        let found = self
+            .signature
+            .find_from(&self.haystack[..self.back], self.front)?;
+        self.front = found + 1;
+        Some(found)
+    }
+
+    /// Finds the last match fully inside the back cursor's range, then pulls the cursor in to
+    /// the end of a match starting one byte earlier, so the next call only finds matches that
+    /// start before this one but may still overlap it.
+    pub fn next_match_back(&mut self) -> Option<usize> {
+        if self.front > self.back {
+            return None;
+        }
+
+        let mut found = None;
+        let mut cursor = self.front;
+        while let Some(pos) = self.signature.find_from(&self.haystack[..self.back], cursor) {
+            found = Some(pos);
+            cursor = pos + 1;
+        }
+
+        let pos = found?;
+        self.back = pos + self.signature.get_elements().len() - 1;
+        Some(pos)
+    }
+}
+
+impl<'a> Iterator for Searcher<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.next_match()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Searcher<'a> {
+    fn next_back(&mut self) -> Option<usize> {
+        self.next_match_back()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_and_backward_probing_interleave() {
+        let haystack = [0x00u8, 0x12, 0x34, 0x56, 0x12, 0x54, 0x12, 0x34, 0x00, 0x55, 0xAA];
+        let signature = Signature::ida("12 34");
+        let mut searcher = signature.search(&haystack);
+
+        assert_eq!(searcher.next_match(), Some(1));
+        assert_eq!(searcher.next_match_back(), Some(6));
+        assert_eq!(searcher.next_match(), None);
+        assert_eq!(searcher.next_match_back(), None);
+    }
+
+    #[test]
+    fn test_all_is_double_ended() {
+        let haystack = [0x00u8, 0x12, 0x34, 0x56, 0x12, 0x54, 0x12, 0x34, 0x00, 0x55, 0xAA];
+        let signature = Signature::ida("12 34");
+
+        assert_eq!(
+            signature.all(&haystack).rev().collect::<Vec<_>>(),
+            [6, 1]
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use crate::Signature;
+
+/// A single state in the [`SignatureSet`] trie: concrete byte transitions, a single wildcard
+/// transition taken regardless of the next byte, and the signatures whose pattern ends here.
+#[derive(Default)]
+struct TrieNode {
+    children: HashMap<u8, usize>,
+    wildcard: Option<usize>,
+    matches: Vec<usize>,
+}
+
+/// Matches many [`Signature`]s against a slice in a single pass.
+///
+/// Patterns are compiled into a byte-level trie (an Aho-Corasick-style automaton extended with
+/// a single wildcard transition) so that a prefix shared between signatures is walked only once
+/// per starting position; each signature the walk completes is then re-verified in full. This
+/// is the efficient way to probe a module for dozens of known signatures at once,
+/// rather than running [`Signature::all`] separately for each one.
+pub struct SignatureSet {
+    signatures: Vec<Signature>,
+    nodes: Vec<TrieNode>,
+}
+
+impl SignatureSet {
+    /// Compiles the given signatures into a shared trie.
+    pub fn new(signatures: Vec<Signature>) -> Self {
+        let mut nodes = vec![TrieNode::default()];
+
+        for (index, signature) in signatures.iter().enumerate() {
+            let mut node = 0usize;
+            for element in signature.get_elements() {
+                // Nibble-masked elements aren't concrete enough to key a trie transition on, so
+                // they are routed through the wildcard branch just like full wildcards; `matches`
+                // re-verifies the whole pattern before reporting a match, so this never causes a
+                // false positive, only a slightly wider trie walk.
+                let existing = if element.is_concrete() {
+                    nodes[node].children.get(&element.value()).copied()
+                } else {
+                    nodes[node].wildcard
+                };
+
+                node = existing.unwrap_or_else(|| {
+                    nodes.push(TrieNode::default());
+                    let child = nodes.len() - 1;
+                    if element.is_concrete() {
+                        nodes[node].children.insert(element.value(), child);
+                    } else {
+                        nodes[node].wildcard = Some(child);
+                    }
+                    child
+                });
+            }
+            nodes[node].matches.push(index);
+        }
+
+        Self { signatures, nodes }
+    }
+
+    /// Returns the signature that was compiled in at `index`.
+    pub fn get(&self, index: usize) -> &Signature {
+        &self.signatures[index]
+    }
+
+    /// Returns the indices of every signature that matches `slice` anchored at its start.
+    pub fn matches(&self, slice: &[u8]) -> Vec<usize> {
+        let mut results = Vec::new();
+        let mut active = vec![0usize];
+        self.collect_verified_matches(0, slice, &mut results);
+
+        for &byte in slice {
+            let mut next_active = Vec::new();
+            for &node in &active {
+                if let Some(&child) = self.nodes[node].children.get(&byte) {
+                    next_active.push(child);
+                }
+                if let Some(wildcard) = self.nodes[node].wildcard {
+                    next_active.push(wildcard);
+                }
+            }
+
+            if next_active.is_empty() {
+                break;
+            }
+
+            for &node in &next_active {
+                self.collect_verified_matches(node, slice, &mut results);
+            }
+            active = next_active;
+        }
+
+        results
+    }
+
+    /// Appends the signatures completed at `node` to `results`, re-checking each one against
+    /// `slice` in full. The trie walk alone isn't precise for nibble-masked elements (it treats
+    /// them as wildcards), so this is what actually guarantees no false positives.
+    fn collect_verified_matches(&self, node: usize, slice: &[u8], results: &mut Vec<usize>) {
+        for &index in &self.nodes[node].matches {
+            let pattern_len = self.signatures[index].get_elements().len();
+            if self.signatures[index].matches(&slice[..pattern_len]) {
+                results.push(index);
+            }
+        }
+    }
+
+    /// Appends the signatures completed at `node` to `results` as `(signature_index, start)`,
+    /// re-checking each one against `slice[start..]` in full for the same reason as
+    /// [`Self::collect_verified_matches`].
+    fn collect_verified_matches_at(
+        &self,
+        node: usize,
+        start: usize,
+        slice: &[u8],
+        results: &mut Vec<(usize, usize)>,
+    ) {
+        for &index in &self.nodes[node].matches {
+            let pattern_len = self.signatures[index].get_elements().len();
+            if start + pattern_len <= slice.len()
+                && self.signatures[index].matches(&slice[start..start + pattern_len])
+            {
+                results.push((index, start));
+            }
+        }
+    }
+
+    /// Finds every `(signature_index, offset)` match in `slice` in a single left-to-right pass:
+    /// a new trie walk is started at every position, and all in-progress walks are advanced
+    /// together by each byte, so the scan never revisits a byte once it's been consumed by a
+    /// given walk. A walk dies as soon as it has no transition for the current byte, which bounds
+    /// the number of walks alive at once by the number of trie nodes rather than by `slice`.
+    pub fn all<'a>(&'a self, slice: &'a [u8]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let mut results = Vec::new();
+        let mut active: Vec<(usize, usize)> = Vec::new();
+
+        for (i, &byte) in slice.iter().enumerate() {
+            self.collect_verified_matches_at(0, i, slice, &mut results);
+            active.push((0, i));
+
+            let mut next_active = Vec::with_capacity(active.len());
+            for (node, start) in active.drain(..) {
+                if let Some(&child) = self.nodes[node].children.get(&byte) {
+                    self.collect_verified_matches_at(child, start, slice, &mut results);
+                    next_active.push((child, start));
+                }
+                if let Some(wildcard) = self.nodes[node].wildcard {
+                    self.collect_verified_matches_at(wildcard, start, slice, &mut results);
+                    next_active.push((wildcard, start));
+                }
+            }
+            active = next_active;
+        }
+
+        results.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Signature;
+
+    fn set() -> SignatureSet {
+        SignatureSet::new(vec![
+            Signature::ida("12 34"),
+            Signature::ida("12 ? 56"),
+            Signature::string("wor", false),
+        ])
+    }
+
+    #[test]
+    fn test_matches_anchored_at_start() {
+        assert_eq!(set().matches(&[0x12, 0x34, 0x00]), [0]);
+        assert_eq!(set().matches(&[0x12, 0x99, 0x56]), [1]);
+        assert_eq!(set().matches(&[0x00, 0x12, 0x34]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_all_reports_every_signature_and_offset() {
+        let haystack = "Hello, world!".as_bytes();
+        assert_eq!(set().all(haystack).collect::<Vec<_>>(), [(2, 7)]);
+
+        let bytes = [0x00u8, 0x12, 0x34, 0x56, 0x12, 0x99, 0x56];
+        let mut matches = set().all(&bytes).collect::<Vec<_>>();
+        matches.sort();
+        assert_eq!(matches, [(0, 1), (1, 1), (1, 4)]);
+    }
+}